@@ -0,0 +1,94 @@
+use aws_credential_types::provider::ProvideCredentials;
+use chrono::{DateTime, Duration, Utc};
+use std::env;
+
+/// Resolves the expiry of the current credentials, preferring the
+/// resolved provider's own expiry (present for STS/assumed-role sessions)
+/// and falling back to the `AWS_SESSION_EXPIRATION` / `AWSUME_EXPIRATION`
+/// environment variables set by tools like aws-vault and awsume.
+pub async fn resolve_expiry(provider: &impl ProvideCredentials) -> Option<DateTime<Utc>> {
+    if let Ok(creds) = provider.provide_credentials().await {
+        if let Some(expiry) = creds.expiry() {
+            return Some(DateTime::<Utc>::from(expiry));
+        }
+    }
+    expiry_from_env()
+}
+
+/// Reads a session expiry from `AWS_SESSION_EXPIRATION` or
+/// `AWSUME_EXPIRATION`, in RFC3339 format.
+pub fn expiry_from_env() -> Option<DateTime<Utc>> {
+    for var in ["AWS_SESSION_EXPIRATION", "AWSUME_EXPIRATION"] {
+        if let Ok(value) = env::var(var) {
+            if let Ok(parsed) = DateTime::parse_from_rfc3339(&value) {
+                return Some(parsed.with_timezone(&Utc));
+            }
+        }
+    }
+    None
+}
+
+/// Prints a human-readable countdown to credential expiry.
+pub fn print_expiry_countdown(expiry: DateTime<Utc>, now: DateTime<Utc>) {
+    let remaining = expiry - now;
+    if remaining <= Duration::zero() {
+        println!("\nWarning: credentials have already expired ({})", expiry);
+        return;
+    }
+    println!(
+        "\nCredentials expire at {} ({}m {}s remaining)",
+        expiry,
+        remaining.num_minutes(),
+        remaining.num_seconds() % 60
+    );
+}
+
+/// Returns true if, extrapolating from the pages fetched so far, the
+/// scan risks outliving the remaining credential lifetime.
+pub fn scan_may_outlast_credentials(
+    expiry: DateTime<Utc>,
+    now: DateTime<Utc>,
+    elapsed_since_start: Duration,
+    pages_fetched: u64,
+) -> bool {
+    if pages_fetched == 0 {
+        return false;
+    }
+    let remaining = expiry - now;
+    if remaining <= Duration::zero() {
+        return true;
+    }
+    let avg_page_time = elapsed_since_start / pages_fetched as i32;
+    avg_page_time * 2 > remaining
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn print_expiry_countdown_does_not_panic_on_past_expiry() {
+        let now = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        let expiry = now - Duration::minutes(5);
+        print_expiry_countdown(expiry, now);
+    }
+
+    #[test]
+    fn scan_may_outlast_credentials_flags_when_remaining_time_is_tight() {
+        let now = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        let expiry = now + Duration::seconds(30);
+        let elapsed = Duration::seconds(20);
+
+        assert!(scan_may_outlast_credentials(expiry, now, elapsed, 1));
+    }
+
+    #[test]
+    fn scan_may_outlast_credentials_is_false_with_plenty_of_headroom() {
+        let now = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        let expiry = now + Duration::hours(1);
+        let elapsed = Duration::seconds(5);
+
+        assert!(!scan_may_outlast_credentials(expiry, now, elapsed, 1));
+    }
+}
@@ -0,0 +1,132 @@
+use crate::credentials;
+use crate::rate_limiter::RateLimiter;
+use aws_sdk_s3::Client;
+use chrono::{DateTime, Utc};
+use std::error::Error;
+use std::future::Future;
+
+/// Lists the `last_modified` timestamps of objects under a bucket/prefix.
+/// Abstracted so the statistics logic can be exercised against an
+/// in-memory fixture instead of a real S3 endpoint. `limiter` gates each
+/// underlying request so callers can cap the aggregate S3 request rate
+/// across many concurrently-scanned prefixes. The returned future is
+/// `Send` so implementations can be driven from inside `tokio::spawn`.
+pub trait ObjectLister {
+    fn list(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        limiter: &RateLimiter,
+    ) -> impl Future<Output = Result<Vec<DateTime<Utc>>, Box<dyn Error + Send + Sync>>> + Send;
+}
+
+/// Lists objects from a real S3 (or S3-compatible) endpoint, paginating
+/// through continuation tokens. If `credential_expiry` is set, warns when
+/// the scan risks outliving the remaining credential lifetime.
+pub struct S3ObjectLister {
+    pub client: Client,
+    pub credential_expiry: Option<DateTime<Utc>>,
+}
+
+impl ObjectLister for S3ObjectLister {
+    async fn list(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        limiter: &RateLimiter,
+    ) -> Result<Vec<DateTime<Utc>>, Box<dyn Error + Send + Sync>> {
+        let mut timestamps: Vec<DateTime<Utc>> = Vec::new();
+        let mut continuation_token = None;
+        let scan_start = Utc::now();
+        let mut pages_fetched: u64 = 0;
+        let mut warned = false;
+
+        loop {
+            limiter.until_ready().await;
+
+            let resp = self
+                .client
+                .list_objects_v2()
+                .bucket(bucket)
+                .prefix(prefix)
+                .set_continuation_token(continuation_token.clone())
+                .send()
+                .await?;
+            pages_fetched += 1;
+
+            if let Some(contents) = resp.contents {
+                for object in contents {
+                    if let Some(last_modified) = object.last_modified {
+                        let last_modified_str = last_modified.to_string();
+
+                        let datetime =
+                            DateTime::parse_from_rfc3339(&last_modified_str)?.with_timezone(&Utc);
+
+                        timestamps.push(datetime);
+                    }
+                }
+            }
+
+            if let Some(expiry) = self.credential_expiry {
+                if !warned {
+                    let now = Utc::now();
+                    let elapsed = now - scan_start;
+                    if credentials::scan_may_outlast_credentials(expiry, now, elapsed, pages_fetched) {
+                        println!(
+                            "\nWarning: credentials expire at {} and the scan may not finish in time.",
+                            expiry
+                        );
+                        warned = true;
+                    }
+                }
+            }
+
+            if resp.is_truncated.unwrap_or(false) {
+                continuation_token = resp.next_continuation_token;
+            } else {
+                break;
+            }
+        }
+
+        Ok(timestamps)
+    }
+}
+
+/// An in-memory lister that returns a fixed set of timestamps, for tests.
+#[cfg(test)]
+pub struct MockObjectLister {
+    pub timestamps: Vec<DateTime<Utc>>,
+}
+
+#[cfg(test)]
+impl ObjectLister for MockObjectLister {
+    async fn list(
+        &self,
+        _bucket: &str,
+        _prefix: &str,
+        _limiter: &RateLimiter,
+    ) -> Result<Vec<DateTime<Utc>>, Box<dyn Error + Send + Sync>> {
+        Ok(self.timestamps.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rate_limiter;
+    use chrono::TimeZone;
+
+    #[tokio::test]
+    async fn mock_lister_returns_fixed_timestamps() {
+        let t1 = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        let t2 = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 5).unwrap();
+        let lister = MockObjectLister {
+            timestamps: vec![t1, t2],
+        };
+        let limiter = rate_limiter::build(100);
+
+        let result = lister.list("bucket", "prefix", &limiter).await.unwrap();
+
+        assert_eq!(result, vec![t1, t2]);
+    }
+}
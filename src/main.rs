@@ -1,10 +1,28 @@
+mod credentials;
+mod lister;
+mod otel;
+mod rate_limiter;
+mod scan;
+mod stats;
+mod time_source;
+
 use aws_config::default_provider::credentials::DefaultCredentialsChain;
 use aws_config::default_provider::region::DefaultRegionChain;
-use aws_sdk_s3::config::BehaviorVersion;
+use aws_sdk_s3::config::{BehaviorVersion, Region};
 use aws_sdk_s3::Client;
-use chrono::{DateTime, Duration, Utc};
-use clap::Parser;
+use chrono::{DateTime, Utc};
+use clap::{Parser, ValueEnum};
+use lister::{ObjectLister, S3ObjectLister};
 use std::error::Error;
+use std::sync::Arc;
+use time_source::{SystemTimeSource, TimeSource};
+
+/// Output format for the scan report.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    Text,
+    Json,
+}
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
@@ -13,93 +31,138 @@ struct Args {
     profile: String,
     #[arg(short, long)]
     bucket: String,
+    /// Prefix to scan; may be repeated to scan multiple prefixes
+    /// concurrently (e.g. `--prefix a/ --prefix b/`).
+    #[arg(long)]
+    prefix: Vec<String>,
+    /// File with one prefix per line, merged with any `--prefix` values.
+    #[arg(long)]
+    prefix_file: Option<String>,
+    /// Maximum number of prefixes scanned concurrently.
+    #[arg(long, default_value_t = 4)]
+    concurrency: usize,
+    /// Maximum S3 requests per second across all concurrent listers.
+    #[arg(long, default_value_t = 20)]
+    rps: u32,
+    /// Number of equal-width buckets for the inter-arrival-time histogram.
+    #[arg(long, default_value_t = 20)]
+    buckets: usize,
+    /// Bucket width for the throughput-rate table, e.g. `30s`, `1m`, `1h`.
+    #[arg(long, value_parser = stats::parse_duration_spec)]
+    window: Option<i64>,
+    /// Custom S3-compatible endpoint URL (MinIO, Ceph, Garage, ...).
+    #[arg(long)]
+    endpoint_url: Option<String>,
+    /// Use path-style addressing (`endpoint/bucket/key`) instead of
+    /// virtual-hosted style. Required by most S3-compatible servers.
+    #[arg(long, default_value_t = false)]
+    path_style: bool,
+    /// Region to use instead of the profile's resolved region; many
+    /// S3-compatible servers ignore it or require a dummy value.
     #[arg(long)]
-    prefix: String,
+    region_override: Option<String>,
+    /// Output format for the report.
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
+    /// OTLP endpoint to export the gap histogram and object counter to,
+    /// in addition to printing the report.
+    #[arg(long)]
+    otel_endpoint: Option<String>,
 }
 
-#[tokio::main]
-async fn main() -> Result<(), Box<dyn Error>> {
-    let args = Args::parse();
-    let bucket = args.bucket;
-    let profile = args.profile;
-    let prefix = args.prefix;
-
-    println!("bucket: {}", bucket);
-    println!("profile: {}\n", profile);
-    println!("prefix: {}\n", prefix);
-
-    let region = DefaultRegionChain::builder()
-        .profile_name(&profile)
-        .build()
-        .region()
-        .await;
-
-    let creds = DefaultCredentialsChain::builder()
-        .profile_name(&profile)
-        .region(region.clone())
-        .build()
-        .await;
-
-    let config = aws_config::defaults(BehaviorVersion::v2024_03_28())
-        .credentials_provider(creds)
-        .region(region)
-        .load()
-        .await;
-
-    let client = Client::new(&config);
-
-    let mut timestamps: Vec<DateTime<Utc>> = Vec::new();
-
-    let mut continuation_token = None;
-
-    loop {
-        let resp = client
-            .list_objects_v2()
-            .bucket(&bucket)
-            .prefix(&prefix)
-            .set_continuation_token(continuation_token.clone())
-            .send()
-            .await?;
-
-        if let Some(contents) = resp.contents {
-            for object in contents {
-                if let Some(last_modified) = object.last_modified {
-                    let last_modified_str = last_modified.to_string();
+/// Scans `bucket` across `prefixes` (concurrently, rate-limited) against
+/// `lister`, then prints the distribution report over all prefixes
+/// combined, plus the object count of each prefix when more than one
+/// prefix was scanned. Takes a `TimeSource` so the "scan started at" banner can be
+/// driven deterministically in tests.
+#[allow(clippy::too_many_arguments)]
+async fn run_scan<L: ObjectLister + Send + Sync + 'static>(
+    lister: Arc<L>,
+    time_source: &impl TimeSource,
+    bucket: &str,
+    prefixes: &[String],
+    concurrency: usize,
+    rps: u32,
+    buckets: usize,
+    window: Option<i64>,
+    format: OutputFormat,
+    otel_endpoint: Option<&str>,
+) -> Result<(), Box<dyn Error>> {
+    if format == OutputFormat::Text {
+        println!("scan started at: {}", time_source.now());
+    }
 
-                    let datetime =
-                        DateTime::parse_from_rfc3339(&last_modified_str)?.with_timezone(&Utc);
+    let prefix_results = scan::scan_prefixes(lister, bucket, prefixes, concurrency, rps)
+        .await
+        .map_err(|e| e.to_string())?;
 
-                    timestamps.push(datetime);
-                }
-            }
-        }
+    let per_prefix_counts: Vec<(String, usize)> = prefix_results
+        .iter()
+        .map(|r| (r.prefix.clone(), r.timestamps.len()))
+        .collect();
 
-        if resp.is_truncated.unwrap_or(false) {
-            continuation_token = resp.next_continuation_token;
-        } else {
-            break;
-        }
-    }
+    let timestamps: Vec<DateTime<Utc>> = prefix_results
+        .into_iter()
+        .flat_map(|r| r.timestamps)
+        .collect();
 
     if timestamps.len() < 2 {
-        println!("Not enough timestamps to calculate average.");
+        if format == OutputFormat::Text {
+            println!("Not enough timestamps to calculate average.");
+        }
         return Ok(());
     }
 
-    timestamps.sort();
+    let time_diffs = stats::sorted_diffs(&timestamps);
+    let totals = stats::compute_total_stats(&time_diffs);
+    let diffs_ms: Vec<i64> = time_diffs.iter().map(|d| d.num_milliseconds()).collect();
+    let gap_stats = stats::compute_gap_stats(&diffs_ms);
+    let window_stats = window.map(|window_millis| stats::compute_window_stats(&timestamps, window_millis));
 
-    let mut time_diffs: Vec<Duration> = Vec::new();
-    for window in timestamps.windows(2) {
-        if let [prev, next] = window {
-            let duration = *next - *prev;
-            time_diffs.push(duration);
+    if let Some(otel_endpoint) = otel_endpoint {
+        otel::export_metrics(otel_endpoint, &diffs_ms, timestamps.len() as u64)?;
+    }
+
+    match format {
+        OutputFormat::Json => {
+            let per_prefix = (per_prefix_counts.len() > 1).then(|| {
+                per_prefix_counts
+                    .iter()
+                    .map(|(prefix, count)| stats::PrefixCountReport {
+                        prefix: prefix.clone(),
+                        count: *count,
+                    })
+                    .collect()
+            });
+            let window_report = window.zip(window_stats.as_ref());
+            let report = stats::build_report(&timestamps, &totals, &gap_stats, window_report, per_prefix);
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        }
+        OutputFormat::Text => {
+            if per_prefix_counts.len() > 1 {
+                stats::print_prefix_counts(&per_prefix_counts);
+            }
+            print_text_report(&time_diffs, &totals, &diffs_ms, &gap_stats, buckets, window, window_stats.as_ref());
         }
     }
 
-    let total_duration = time_diffs.iter().fold(Duration::zero(), |acc, x| acc + *x);
+    Ok(())
+}
 
-    let avg_duration = total_duration / (time_diffs.len() as i32);
-    let total_millis = total_duration.num_milliseconds();
+/// Prints the original human-readable report: average/total, the gap
+/// distribution, the histogram, and (if requested) the windowed
+/// throughput table.
+#[allow(clippy::too_many_arguments)]
+fn print_text_report(
+    time_diffs: &[chrono::Duration],
+    totals: &stats::TotalStats,
+    diffs_ms: &[i64],
+    gap_stats: &stats::GapStats,
+    buckets: usize,
+    window: Option<i64>,
+    window_stats: Option<&stats::WindowStats>,
+) {
+    let total_millis = totals.total_duration.num_milliseconds();
 
     let hours = total_millis / (1000 * 60 * 60);
     let remaining_millis = total_millis % (1000 * 60 * 60);
@@ -110,7 +173,7 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let seconds = remaining_millis / 1000;
     let milliseconds = remaining_millis % 1000;
 
-    println!("Average time between timestamps: {:?}", avg_duration);
+    println!("Average time between timestamps: {:?}", totals.avg_duration);
     println!(
         "Total time for {:?} files: {}h {}m {}s {}ms",
         time_diffs.len(),
@@ -120,5 +183,147 @@ async fn main() -> Result<(), Box<dyn Error>> {
         milliseconds
     );
 
-    Ok(())
+    println!("\nInter-arrival-time distribution (ms):");
+    println!("  min:    {}", gap_stats.min);
+    println!("  median: {}", gap_stats.median);
+    println!("  p90:    {}", gap_stats.p90);
+    println!("  p95:    {}", gap_stats.p95);
+    println!("  p99:    {}", gap_stats.p99);
+    println!("  max:    {}", gap_stats.max);
+    println!("  mean:   {:.2}", gap_stats.mean);
+    println!("  stddev: {:.2}", gap_stats.stddev);
+
+    stats::print_histogram(diffs_ms, buckets, 40);
+
+    if let (Some(window_millis), Some(window_stats)) = (window, window_stats) {
+        stats::print_window_stats(window_stats, window_millis);
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+    let bucket = args.bucket;
+    let profile = args.profile;
+    let buckets = args.buckets;
+    let window = args.window;
+    let endpoint_url = args.endpoint_url;
+    let path_style = args.path_style;
+    let region_override = args.region_override;
+    let format = args.format;
+    let otel_endpoint = args.otel_endpoint;
+    let concurrency = args.concurrency;
+    let rps = args.rps;
+
+    let mut prefixes = args.prefix;
+    if let Some(prefix_file) = &args.prefix_file {
+        let contents = std::fs::read_to_string(prefix_file)?;
+        prefixes.extend(
+            contents
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(String::from),
+        );
+    }
+    if prefixes.is_empty() {
+        return Err("at least one --prefix (or --prefix-file entry) is required".into());
+    }
+
+    if format == OutputFormat::Text {
+        println!("bucket: {}", bucket);
+        println!("profile: {}\n", profile);
+        println!("prefixes: {}\n", prefixes.join(", "));
+    }
+
+    let region = DefaultRegionChain::builder()
+        .profile_name(&profile)
+        .build()
+        .region()
+        .await;
+    let region = match region_override {
+        Some(r) => Some(Region::new(r)),
+        None => region,
+    };
+
+    let creds = DefaultCredentialsChain::builder()
+        .profile_name(&profile)
+        .region(region.clone())
+        .build()
+        .await;
+
+    let credential_expiry = credentials::resolve_expiry(&creds).await;
+    if let (Some(expiry), OutputFormat::Text) = (credential_expiry, format) {
+        credentials::print_expiry_countdown(expiry, Utc::now());
+    }
+
+    let mut config_loader = aws_config::defaults(BehaviorVersion::v2024_03_28())
+        .credentials_provider(creds)
+        .region(region);
+
+    if let Some(endpoint_url) = &endpoint_url {
+        config_loader = config_loader.endpoint_url(endpoint_url);
+    }
+
+    let config = config_loader.load().await;
+
+    let s3_config = aws_sdk_s3::config::Builder::from(&config)
+        .force_path_style(path_style)
+        .build();
+
+    let client = Client::from_conf(s3_config);
+    let lister = Arc::new(S3ObjectLister {
+        client,
+        credential_expiry,
+    });
+    let time_source = SystemTimeSource;
+
+    run_scan(
+        lister,
+        &time_source,
+        &bucket,
+        &prefixes,
+        concurrency,
+        rps,
+        buckets,
+        window,
+        format,
+        otel_endpoint.as_deref(),
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use lister::MockObjectLister;
+    use time_source::StaticTimeSource;
+
+    #[tokio::test]
+    async fn run_scan_succeeds_against_a_mock_lister() {
+        let t0 = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        let t1 = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 5).unwrap();
+        let lister = Arc::new(MockObjectLister {
+            timestamps: vec![t0, t1],
+        });
+        let time_source = StaticTimeSource(t0);
+        let prefixes = vec!["prefix".to_string()];
+
+        let result = run_scan(
+            lister,
+            &time_source,
+            "bucket",
+            &prefixes,
+            4,
+            20,
+            20,
+            None,
+            OutputFormat::Json,
+            None,
+        )
+        .await;
+
+        assert!(result.is_ok());
+    }
 }
@@ -0,0 +1,43 @@
+use opentelemetry::metrics::MeterProvider as _;
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::metrics::PeriodicReader;
+use opentelemetry_sdk::runtime;
+use std::error::Error;
+
+/// Records the inter-arrival gaps into an OTLP histogram instrument and
+/// the object count into a counter, then exports them to `endpoint`.
+pub fn export_metrics(endpoint: &str, diffs_ms: &[i64], object_count: u64) -> Result<(), Box<dyn Error>> {
+    let exporter = opentelemetry_otlp::MetricExporter::builder()
+        .with_tonic()
+        .with_endpoint(endpoint)
+        .build()?;
+
+    // requires the `rt-tokio` feature on opentelemetry_sdk.
+    let reader = PeriodicReader::builder(exporter, runtime::Tokio).build();
+
+    let provider = opentelemetry_sdk::metrics::SdkMeterProvider::builder()
+        .with_reader(reader)
+        .build();
+
+    let meter = provider.meter("tp-counter");
+
+    let histogram = meter
+        .u64_histogram("tp_counter.inter_arrival_gap_ms")
+        .with_description("Inter-arrival time between consecutive object timestamps")
+        .with_unit("ms")
+        .build();
+    for &gap_ms in diffs_ms {
+        histogram.record(gap_ms.max(0) as u64, &[]);
+    }
+
+    let counter = meter
+        .u64_counter("tp_counter.object_count")
+        .with_description("Number of objects observed in the scan")
+        .build();
+    counter.add(object_count, &[KeyValue::new("source", "tp-counter")]);
+
+    provider.shutdown()?;
+
+    Ok(())
+}
@@ -0,0 +1,13 @@
+use governor::clock::DefaultClock;
+use governor::state::{InMemoryState, NotKeyed};
+use governor::{Quota, RateLimiter as GovernorRateLimiter};
+use std::num::NonZeroU32;
+
+/// A shared token-bucket limiter gating S3 requests per second.
+pub type RateLimiter = GovernorRateLimiter<NotKeyed, InMemoryState, DefaultClock>;
+
+/// Builds a rate limiter allowing `rps` requests per second (minimum 1).
+pub fn build(rps: u32) -> RateLimiter {
+    let quota = Quota::per_second(NonZeroU32::new(rps.max(1)).unwrap());
+    RateLimiter::direct(quota)
+}
@@ -0,0 +1,81 @@
+use crate::lister::ObjectLister;
+use crate::rate_limiter;
+use chrono::{DateTime, Utc};
+use std::error::Error;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+/// The timestamps collected for a single prefix.
+pub struct PrefixResult {
+    pub prefix: String,
+    pub timestamps: Vec<DateTime<Utc>>,
+}
+
+/// Scans `prefixes` concurrently against `lister`, bounded by at most
+/// `concurrency` simultaneous listers and throttled to `rps` requests per
+/// second via a shared token-bucket limiter. The limiter is consulted by
+/// `lister` itself before every `list_objects_v2` call (not just once per
+/// prefix), so the aggregate S3 request rate stays bounded regardless of
+/// how many pages a single prefix needs. Each prefix owns its own
+/// continuation-token loop; results are merged by the caller.
+pub async fn scan_prefixes<L>(
+    lister: Arc<L>,
+    bucket: &str,
+    prefixes: &[String],
+    concurrency: usize,
+    rps: u32,
+) -> Result<Vec<PrefixResult>, Box<dyn Error + Send + Sync>>
+where
+    L: ObjectLister + Send + Sync + 'static,
+{
+    let semaphore = Arc::new(Semaphore::new(concurrency.max(1)));
+    let limiter = Arc::new(rate_limiter::build(rps));
+
+    let mut handles = Vec::with_capacity(prefixes.len());
+    for prefix in prefixes {
+        let lister = Arc::clone(&lister);
+        let semaphore = Arc::clone(&semaphore);
+        let limiter = Arc::clone(&limiter);
+        let bucket = bucket.to_string();
+        let prefix = prefix.clone();
+
+        handles.push(tokio::spawn(async move {
+            let _permit = semaphore.acquire_owned().await?;
+            let timestamps = lister.list(&bucket, &prefix, &limiter).await?;
+            Ok::<PrefixResult, Box<dyn Error + Send + Sync>>(PrefixResult { prefix, timestamps })
+        }));
+    }
+
+    let mut results = Vec::with_capacity(handles.len());
+    for handle in handles {
+        results.push(handle.await??);
+    }
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lister::MockObjectLister;
+    use chrono::TimeZone;
+
+    #[tokio::test]
+    async fn scan_prefixes_returns_one_result_per_prefix() {
+        let t1 = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        let t2 = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 5).unwrap();
+        let lister = Arc::new(MockObjectLister {
+            timestamps: vec![t1, t2],
+        });
+        let prefixes = vec!["a/".to_string(), "b/".to_string()];
+
+        let results = scan_prefixes(lister, "bucket", &prefixes, 2, 100)
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 2);
+        for result in &results {
+            assert_eq!(result.timestamps, vec![t1, t2]);
+        }
+    }
+}
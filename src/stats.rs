@@ -0,0 +1,391 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Parses a duration spec like `30s`, `1m`, `1h` into milliseconds.
+pub fn parse_duration_spec(spec: &str) -> Result<i64, String> {
+    let spec = spec.trim();
+    let (num_part, unit) = spec.split_at(
+        spec.find(|c: char| !c.is_ascii_digit())
+            .ok_or_else(|| format!("missing unit in duration '{}' (expected ms/s/m/h)", spec))?,
+    );
+    let value: i64 = num_part
+        .parse()
+        .map_err(|_| format!("invalid number in duration '{}'", spec))?;
+    if value <= 0 {
+        return Err(format!("duration '{}' must be positive", spec));
+    }
+    let millis = match unit {
+        "ms" => value,
+        "s" => value * 1000,
+        "m" => value * 60 * 1000,
+        "h" => value * 60 * 60 * 1000,
+        other => return Err(format!("unknown duration unit '{}' (expected ms/s/m/h)", other)),
+    };
+    Ok(millis)
+}
+
+/// Sorts `timestamps` and returns the gaps between consecutive entries.
+pub fn sorted_diffs(timestamps: &[DateTime<Utc>]) -> Vec<Duration> {
+    let mut sorted: Vec<DateTime<Utc>> = timestamps.to_vec();
+    sorted.sort();
+
+    let mut time_diffs: Vec<Duration> = Vec::new();
+    for window in sorted.windows(2) {
+        if let [prev, next] = window {
+            time_diffs.push(*next - *prev);
+        }
+    }
+    time_diffs
+}
+
+/// The overall average gap and total span across a set of inter-arrival
+/// gaps.
+pub struct TotalStats {
+    pub avg_duration: Duration,
+    pub total_duration: Duration,
+}
+
+/// Computes the average and total of a set of inter-arrival gaps.
+/// `time_diffs` must be non-empty.
+pub fn compute_total_stats(time_diffs: &[Duration]) -> TotalStats {
+    let total_duration = time_diffs.iter().fold(Duration::zero(), |acc, x| acc + *x);
+    let avg_duration = total_duration / (time_diffs.len() as i32);
+    TotalStats {
+        avg_duration,
+        total_duration,
+    }
+}
+
+/// Summary statistics over a set of inter-arrival gaps, in milliseconds.
+pub struct GapStats {
+    pub min: i64,
+    pub max: i64,
+    pub median: i64,
+    pub p90: i64,
+    pub p95: i64,
+    pub p99: i64,
+    pub mean: f64,
+    pub stddev: f64,
+}
+
+/// Computes min/max/percentiles/stddev over `diffs_ms` using Welford's
+/// online algorithm for variance and the nearest-rank method for
+/// percentiles. `diffs_ms` must be non-empty.
+pub fn compute_gap_stats(diffs_ms: &[i64]) -> GapStats {
+    let mut n: u64 = 0;
+    let mut mean = 0.0_f64;
+    let mut m2 = 0.0_f64;
+    for &x in diffs_ms {
+        n += 1;
+        let d = x as f64 - mean;
+        mean += d / n as f64;
+        m2 += d * (x as f64 - mean);
+    }
+    let variance = m2 / n as f64;
+    let stddev = variance.sqrt();
+
+    let mut sorted: Vec<i64> = diffs_ms.to_vec();
+    sorted.sort();
+
+    let percentile = |p: f64| -> i64 {
+        let len = sorted.len();
+        let idx = ((p / 100.0) * len as f64).ceil() as isize - 1;
+        let idx = idx.clamp(0, len as isize - 1) as usize;
+        sorted[idx]
+    };
+
+    GapStats {
+        min: sorted[0],
+        max: sorted[sorted.len() - 1],
+        median: percentile(50.0),
+        p90: percentile(90.0),
+        p95: percentile(95.0),
+        p99: percentile(99.0),
+        mean,
+        stddev,
+    }
+}
+
+/// Prints an ASCII histogram of `diffs_ms` split into `bucket_count`
+/// equal-width buckets spanning min..=max, with bars scaled to fit
+/// within `max_bar_width` characters.
+pub fn print_histogram(diffs_ms: &[i64], bucket_count: usize, max_bar_width: usize) {
+    if bucket_count == 0 {
+        return;
+    }
+
+    let min = *diffs_ms.iter().min().unwrap();
+    let max = *diffs_ms.iter().max().unwrap();
+
+    let mut counts = vec![0u64; bucket_count];
+    let range = (max - min).max(1) as f64;
+    let width = range / bucket_count as f64;
+
+    for &x in diffs_ms {
+        let idx = (((x - min) as f64 / width) as usize).min(bucket_count - 1);
+        counts[idx] += 1;
+    }
+
+    let peak = *counts.iter().max().unwrap_or(&1);
+
+    println!("\nHistogram of gaps ({} buckets):", bucket_count);
+    for (i, &count) in counts.iter().enumerate() {
+        let bucket_start = min as f64 + i as f64 * width;
+        let bucket_end = bucket_start + width;
+        let bar_len = if peak == 0 {
+            0
+        } else {
+            ((count as f64 / peak as f64) * max_bar_width as f64).round() as usize
+        };
+        println!(
+            "  [{:>10.0}ms, {:>10.0}ms) {:>6} {}",
+            bucket_start,
+            bucket_end,
+            count,
+            "#".repeat(bar_len)
+        );
+    }
+}
+
+/// Per-window object counts and summary rates for a windowed scan.
+pub struct WindowStats {
+    pub counts: BTreeMap<i64, u64>,
+    pub peak: u64,
+    pub mean: f64,
+    pub objects_per_sec: f64,
+    pub objects_per_min: f64,
+}
+
+/// Buckets `timestamps` into fixed-width windows of `window_millis`
+/// spanning min..max and reports per-window counts plus peak/mean/overall
+/// rates. Every window in that span is present in `counts`, including
+/// ones with no objects, so a zero-count window can't be mistaken for a
+/// missing one. `timestamps` must be non-empty.
+pub fn compute_window_stats(timestamps: &[DateTime<Utc>], window_millis: i64) -> WindowStats {
+    let mut sorted: Vec<DateTime<Utc>> = timestamps.to_vec();
+    sorted.sort();
+
+    let start = sorted[0];
+    let span_millis_raw = (*sorted.last().unwrap() - start).num_milliseconds();
+    let window_count = span_millis_raw / window_millis + 1;
+
+    let mut counts: BTreeMap<i64, u64> = (0..window_count).map(|idx| (idx, 0)).collect();
+    for ts in &sorted {
+        let idx = (*ts - start).num_milliseconds() / window_millis;
+        *counts.entry(idx).or_insert(0) += 1;
+    }
+
+    let peak = *counts.values().max().unwrap_or(&0);
+    let mean = sorted.len() as f64 / window_count as f64;
+
+    let span_millis = span_millis_raw.max(1);
+    let span_secs = span_millis as f64 / 1000.0;
+    let objects_per_sec = sorted.len() as f64 / span_secs;
+    let objects_per_min = objects_per_sec * 60.0;
+
+    WindowStats {
+        counts,
+        peak,
+        mean,
+        objects_per_sec,
+        objects_per_min,
+    }
+}
+
+/// Prints the per-window table and overall throughput summary.
+pub fn print_window_stats(stats: &WindowStats, window_millis: i64) {
+    println!("\nThroughput by {}ms window:", window_millis);
+    for (idx, count) in &stats.counts {
+        let window_start_ms = idx * window_millis;
+        println!("  window {:>6} (+{:>10}ms): {} objects", idx, window_start_ms, count);
+    }
+    println!("  peak window:      {} objects", stats.peak);
+    println!("  mean per window:  {:.2} objects", stats.mean);
+    println!("  objects/sec:      {:.4}", stats.objects_per_sec);
+    println!("  objects/min:      {:.2}", stats.objects_per_min);
+}
+
+/// Prints the object count contributed by each scanned prefix.
+pub fn print_prefix_counts(counts: &[(String, usize)]) {
+    println!("\nObjects per prefix:");
+    for (prefix, count) in counts {
+        println!("  {:<40} {}", prefix, count);
+    }
+}
+
+/// Percentile/spread summary of the inter-arrival gaps, in milliseconds.
+#[derive(Serialize)]
+pub struct PercentileReport {
+    pub min: i64,
+    pub median: i64,
+    pub p90: i64,
+    pub p95: i64,
+    pub p99: i64,
+    pub max: i64,
+    pub mean: f64,
+    pub stddev: f64,
+}
+
+/// Object count for a single throughput window.
+#[derive(Serialize)]
+pub struct WindowReport {
+    pub window_index: i64,
+    pub count: u64,
+}
+
+/// Windowed throughput rates, mirroring what the text report prints:
+/// the window width, the per-window series, and the peak/mean/overall
+/// rates derived from it.
+#[derive(Serialize)]
+pub struct WindowsReport {
+    pub window_millis: i64,
+    pub series: Vec<WindowReport>,
+    pub peak: u64,
+    pub mean: f64,
+    pub objects_per_sec: f64,
+    pub objects_per_min: f64,
+}
+
+/// Object count contributed by a single scanned prefix.
+#[derive(Serialize)]
+pub struct PrefixCountReport {
+    pub prefix: String,
+    pub count: usize,
+}
+
+/// Machine-readable summary of a scan, for `--format json`.
+#[derive(Serialize)]
+pub struct Report {
+    pub count: usize,
+    pub min_timestamp: DateTime<Utc>,
+    pub max_timestamp: DateTime<Utc>,
+    pub avg_gap_ms: f64,
+    pub total_duration_ms: i64,
+    pub percentiles: PercentileReport,
+    pub windows: Option<WindowsReport>,
+    pub per_prefix: Option<Vec<PrefixCountReport>>,
+}
+
+/// Assembles a `Report` from the already-computed gap and window
+/// statistics. `timestamps` must be non-empty.
+pub fn build_report(
+    timestamps: &[DateTime<Utc>],
+    totals: &TotalStats,
+    gap_stats: &GapStats,
+    window: Option<(i64, &WindowStats)>,
+    per_prefix: Option<Vec<PrefixCountReport>>,
+) -> Report {
+    let mut sorted: Vec<DateTime<Utc>> = timestamps.to_vec();
+    sorted.sort();
+
+    Report {
+        count: sorted.len(),
+        min_timestamp: sorted[0],
+        max_timestamp: sorted[sorted.len() - 1],
+        avg_gap_ms: totals.avg_duration.num_milliseconds() as f64,
+        total_duration_ms: totals.total_duration.num_milliseconds(),
+        percentiles: PercentileReport {
+            min: gap_stats.min,
+            median: gap_stats.median,
+            p90: gap_stats.p90,
+            p95: gap_stats.p95,
+            p99: gap_stats.p99,
+            max: gap_stats.max,
+            mean: gap_stats.mean,
+            stddev: gap_stats.stddev,
+        },
+        windows: window.map(|(window_millis, w)| WindowsReport {
+            window_millis,
+            series: w
+                .counts
+                .iter()
+                .map(|(&window_index, &count)| WindowReport { window_index, count })
+                .collect(),
+            peak: w.peak,
+            mean: w.mean,
+            objects_per_sec: w.objects_per_sec,
+            objects_per_min: w.objects_per_min,
+        }),
+        per_prefix,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn ts(secs: i64) -> DateTime<Utc> {
+        Utc.timestamp_opt(secs, 0).unwrap()
+    }
+
+    #[test]
+    fn parse_duration_spec_parses_units() {
+        assert_eq!(parse_duration_spec("500ms").unwrap(), 500);
+        assert_eq!(parse_duration_spec("30s").unwrap(), 30_000);
+        assert_eq!(parse_duration_spec("1m").unwrap(), 60_000);
+        assert_eq!(parse_duration_spec("2h").unwrap(), 7_200_000);
+        assert!(parse_duration_spec("banana").is_err());
+    }
+
+    #[test]
+    fn parse_duration_spec_rejects_non_positive_values() {
+        assert!(parse_duration_spec("0s").is_err());
+        assert!(parse_duration_spec("0ms").is_err());
+    }
+
+    #[test]
+    fn sorted_diffs_handles_unsorted_input() {
+        let timestamps = vec![ts(10), ts(0), ts(5)];
+        let diffs = sorted_diffs(&timestamps);
+        assert_eq!(diffs, vec![Duration::seconds(5), Duration::seconds(5)]);
+    }
+
+    #[test]
+    fn compute_gap_stats_matches_known_values() {
+        let diffs_ms = vec![100, 200, 300, 400, 500];
+        let stats = compute_gap_stats(&diffs_ms);
+        assert_eq!(stats.min, 100);
+        assert_eq!(stats.max, 500);
+        assert_eq!(stats.median, 300);
+        assert_eq!(stats.mean, 300.0);
+        assert!((stats.stddev - 141.421356).abs() < 1e-3);
+    }
+
+    #[test]
+    fn compute_window_stats_buckets_by_fixed_width() {
+        let timestamps = vec![ts(0), ts(1), ts(61), ts(62)];
+        let stats = compute_window_stats(&timestamps, 60_000);
+        assert_eq!(stats.counts.get(&0), Some(&2));
+        assert_eq!(stats.counts.get(&1), Some(&2));
+        assert_eq!(stats.peak, 2);
+    }
+
+    #[test]
+    fn compute_window_stats_fills_empty_windows_in_the_span() {
+        let timestamps = vec![ts(0), ts(125)];
+        let stats = compute_window_stats(&timestamps, 60_000);
+        assert_eq!(stats.counts.len(), 3);
+        assert_eq!(stats.counts.get(&0), Some(&1));
+        assert_eq!(stats.counts.get(&1), Some(&0));
+        assert_eq!(stats.counts.get(&2), Some(&1));
+        assert!((stats.mean - (2.0 / 3.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn build_report_summarizes_count_and_span() {
+        let timestamps = vec![ts(0), ts(5), ts(10)];
+        let diffs = sorted_diffs(&timestamps);
+        let totals = compute_total_stats(&diffs);
+        let diffs_ms: Vec<i64> = diffs.iter().map(|d| d.num_milliseconds()).collect();
+        let gap_stats = compute_gap_stats(&diffs_ms);
+
+        let report = build_report(&timestamps, &totals, &gap_stats, None, None);
+
+        assert_eq!(report.count, 3);
+        assert_eq!(report.min_timestamp, ts(0));
+        assert_eq!(report.max_timestamp, ts(10));
+        assert!(report.windows.is_none());
+    }
+}
@@ -0,0 +1,41 @@
+use chrono::{DateTime, Utc};
+
+/// A source of "now", abstracted so callers can inject a fixed clock in
+/// tests instead of depending on wall-clock time.
+pub trait TimeSource {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// The real clock, backed by `Utc::now()`.
+pub struct SystemTimeSource;
+
+impl TimeSource for SystemTimeSource {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// A clock that always returns the same instant, for deterministic tests.
+#[cfg(test)]
+pub struct StaticTimeSource(pub DateTime<Utc>);
+
+#[cfg(test)]
+impl TimeSource for StaticTimeSource {
+    fn now(&self) -> DateTime<Utc> {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn static_time_source_always_returns_same_instant() {
+        let fixed = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        let source = StaticTimeSource(fixed);
+        assert_eq!(source.now(), fixed);
+        assert_eq!(source.now(), fixed);
+    }
+}